@@ -1,8 +1,14 @@
-use crate::dynamics::RawJointSet;
+//! This rapier version's `JointParams` is a closed enum with exactly four variants: `BallJoint`,
+//! `FixedJoint`, `PrismaticJoint`, and the dim3-only `RevoluteJoint`. There is no unified,
+//! per-axis joint model (no `GenericJoint`, no axis mask/lock/limit/motor configured uniformly
+//! across joint types) to build such a thing on top of. Axis locking, limits, and motors are
+//! each configured per concrete joint type by the functions below instead.
+
+use crate::dynamics::{RawJointSet, RawRigidBodySet};
 use crate::math::{RawRotation, RawVector};
 use na::Unit;
 use rapier::dynamics::{BallJoint, FixedJoint, JointParams, PrismaticJoint, SpringModel};
-use rapier::math::Isometry;
+use rapier::math::{Isometry, Point};
 #[cfg(feature = "dim2")]
 use rapier::math::Rotation;
 use wasm_bindgen::prelude::*;
@@ -11,8 +17,59 @@ use {
     na::{Matrix3, Quaternion, UnitQuaternion, Vector3},
     rapier::dynamics::RevoluteJoint,
     rapier::utils::WBasis,
+    std::cell::RefCell,
+    std::collections::HashMap,
 };
 
+/// The local-space anchor point of a joint on its first attached rigid-body.
+fn local_anchor1(params: &JointParams) -> Point<f32> {
+    match params {
+        JointParams::BallJoint(b) => b.local_anchor1,
+        #[cfg(feature = "dim3")]
+        JointParams::RevoluteJoint(r) => r.local_anchor1,
+        JointParams::PrismaticJoint(p) => p.local_anchor1,
+        JointParams::FixedJoint(f) => f.local_frame1.translation.vector.into(),
+    }
+}
+
+/// The local-space anchor point of a joint on its second attached rigid-body.
+fn local_anchor2(params: &JointParams) -> Point<f32> {
+    match params {
+        JointParams::BallJoint(b) => b.local_anchor2,
+        #[cfg(feature = "dim3")]
+        JointParams::RevoluteJoint(r) => r.local_anchor2,
+        JointParams::PrismaticJoint(p) => p.local_anchor2,
+        JointParams::FixedJoint(f) => f.local_frame2.translation.vector.into(),
+    }
+}
+
+/// Builds the local-space frame of a revolute joint anchor: its origin is the anchor point and
+/// its rotation aligns the joint axis to the frame's local `x` axis, the same convention used by
+/// `jointFrameX1`/`jointFrameX2`.
+///
+/// `RevoluteJoint` doesn't expose a stored reference/tangent basis to reuse here (only
+/// `local_axis1`/`local_axis2`), so like `jointFrameX1`/`jointFrameX2` this recomputes an
+/// arbitrary orthonormal basis around the axis. That basis is consistent from call to call but
+/// not tied to the joint's configured rest orientation, so it's only meaningful as a relative
+/// reference between `frame1` and `frame2`, not as an absolute zero.
+#[cfg(feature = "dim3")]
+fn axis_local_frame(anchor: Point<f32>, axis: Unit<Vector3<f32>>) -> Isometry<f32> {
+    let basis_a = axis.orthonormal_basis()[0];
+    let basis_b = axis.cross(&basis_a);
+    let rotmat = na::Rotation3::from_matrix_unchecked(Matrix3::from_columns(&[*axis, basis_a, basis_b]));
+    Isometry::from_parts(anchor.coords.into(), UnitQuaternion::from(rotmat))
+}
+
+// `jointAngle` unwraps its `]-π, π]` reading into a continuous turn count across calls. `Joint`
+// has no scratch field to hold that state, so it's kept here instead, external to the joint
+// itself, keyed by joint handle. A joint removed from the set (and its handle index reused by a
+// later joint) will pick up a stale entry; that's an accepted tradeoff of per-handle state with
+// no removal hook to clear it.
+#[cfg(feature = "dim3")]
+thread_local! {
+    static JOINT_ANGLE_STATE: RefCell<HashMap<u32, (f32, i32)>> = RefCell::new(HashMap::new());
+}
+
 #[wasm_bindgen]
 pub enum RawJointType {
     Ball,
@@ -21,6 +78,11 @@ pub enum RawJointType {
     Revolute,
 }
 
+// A gear joint couples two existing joints by a ratio rather than describing a constraint
+// between two rigid-bodies on its own, which doesn't fit `JointParams` (closed at the four
+// variants above, each relating exactly one body pair) or the solver built around it. Adding one
+// would need a rapier fork, not a binding-side addition.
+
 #[wasm_bindgen]
 pub enum RawSpringModel {
     Disabled,
@@ -192,7 +254,131 @@ impl RawJointSet {
         })
     }
 
+    /// The current angle, in radians, of a revolute joint along its free rotation axis.
+    ///
+    /// Successive calls are continuous across the `]-π, π]` wraparound: the reading accumulates a
+    /// turn count rather than snapping from π back to −π as the joint keeps spinning the same
+    /// way. Returns `0.0` for joint types without a single rotational free axis (and isn't
+    /// unwrapped against prior calls for those, since there's nothing to unwrap).
+    #[cfg(feature = "dim3")]
+    pub fn jointAngle(&self, bodies: &RawRigidBodySet, handle: u32) -> f32 {
+        let wrapped = self.map(handle, |j| {
+            let (axis1, axis2) = match &j.params {
+                JointParams::RevoluteJoint(r) => (*r.local_axis1, *r.local_axis2),
+                _ => return None,
+            };
+
+            let anchor1 = local_anchor1(&j.params);
+            let anchor2 = local_anchor2(&j.params);
+            let frame1 = *bodies.0[j.body1].position() * axis_local_frame(anchor1, axis1);
+            let frame2 = *bodies.0[j.body2].position() * axis_local_frame(anchor2, axis2);
+            let rel_rotation = frame1.rotation.inverse() * frame2.rotation;
+
+            // Both frames align the joint axis to their local `x`; the rotation about that shared
+            // axis shows up as how much `y` got rotated towards `z`, so its signed angle is the
+            // `atan2` of the rotated tangent's `z` and `y` components.
+            let tangent = rel_rotation * Vector3::y();
+            Some(tangent.z.atan2(tangent.y))
+        });
+
+        let wrapped = match wrapped {
+            Some(wrapped) => wrapped,
+            None => return 0.0,
+        };
+
+        JOINT_ANGLE_STATE.with(|state| {
+            let mut state = state.borrow_mut();
+            let (last, turns) = state.entry(handle).or_insert((wrapped, 0));
+            let delta = wrapped - *last;
+            if delta > std::f32::consts::PI {
+                *turns -= 1;
+            } else if delta < -std::f32::consts::PI {
+                *turns += 1;
+            }
+            *last = wrapped;
+            wrapped + *turns as f32 * (2.0 * std::f32::consts::PI)
+        })
+    }
+
+    /// The current displacement, along its free translation axis, of a prismatic joint.
+    ///
+    /// Returns `0.0` for joint types without a single translational free axis.
+    pub fn jointTranslation(&self, bodies: &RawRigidBodySet, handle: u32) -> f32 {
+        self.map(handle, |j| match &j.params {
+            JointParams::PrismaticJoint(p) => {
+                let anchor1 = *bodies.0[j.body1].position() * p.local_anchor1;
+                let anchor2 = *bodies.0[j.body2].position() * p.local_anchor2;
+                let axis1 = *bodies.0[j.body1].position() * *p.local_axis1();
+                axis1.dot(&(anchor2 - anchor1))
+            }
+            _ => 0.0,
+        })
+    }
+
+    /// The angular velocity of the second rigid-body relative to the first, evaluated at the
+    /// joint's anchors: `ω2 - ω1`.
+    #[cfg(feature = "dim3")]
+    pub fn jointRelativeAngularVelocity(&self, bodies: &RawRigidBodySet, handle: u32) -> RawVector {
+        self.map(handle, |j| {
+            let body1 = &bodies.0[j.body1];
+            let body2 = &bodies.0[j.body2];
+            RawVector(body2.angvel() - body1.angvel())
+        })
+    }
+
+    /// The angular velocity of the second rigid-body relative to the first: `ω2 - ω1`.
+    #[cfg(feature = "dim2")]
+    pub fn jointRelativeAngularVelocity(&self, bodies: &RawRigidBodySet, handle: u32) -> f32 {
+        self.map(handle, |j| {
+            let body1 = &bodies.0[j.body1];
+            let body2 = &bodies.0[j.body2];
+            body2.angvel() - body1.angvel()
+        })
+    }
+
+    /// The linear velocity of the second rigid-body relative to the first, evaluated at the
+    /// joint's anchors: `v2 + ω2×r2 − (v1 + ω1×r1)`.
+    #[cfg(feature = "dim3")]
+    pub fn jointRelativeLinearVelocity(&self, bodies: &RawRigidBodySet, handle: u32) -> RawVector {
+        self.map(handle, |j| {
+            let body1 = &bodies.0[j.body1];
+            let body2 = &bodies.0[j.body2];
+            // `position() * anchor` is a `Point`; take `.coords` to get the lever arm as a
+            // `Vector3`, which is what `Vector3::cross` actually takes.
+            let r1 = (*body1.position() * local_anchor1(&j.params)).coords
+                - body1.position().translation.vector;
+            let r2 = (*body2.position() * local_anchor2(&j.params)).coords
+                - body2.position().translation.vector;
+            let vel1 = body1.linvel() + body1.angvel().cross(&r1);
+            let vel2 = body2.linvel() + body2.angvel().cross(&r2);
+            RawVector(vel2 - vel1)
+        })
+    }
+
+    /// The linear velocity of the second rigid-body relative to the first, evaluated at the
+    /// joint's anchors: `v2 + ω2×r2 − (v1 + ω1×r1)`.
+    #[cfg(feature = "dim2")]
+    pub fn jointRelativeLinearVelocity(&self, bodies: &RawRigidBodySet, handle: u32) -> RawVector {
+        self.map(handle, |j| {
+            let body1 = &bodies.0[j.body1];
+            let body2 = &bodies.0[j.body2];
+            let r1 = (*body1.position() * local_anchor1(&j.params)).coords
+                - body1.position().translation.vector;
+            let r2 = (*body2.position() * local_anchor2(&j.params)).coords
+                - body2.position().translation.vector;
+            let angvel1 = body1.angvel();
+            let angvel2 = body2.angvel();
+            let vel1 = body1.linvel() + na::Vector2::new(-angvel1 * r1.y, angvel1 * r1.x);
+            let vel2 = body2.linvel() + na::Vector2::new(-angvel2 * r2.y, angvel2 * r2.x);
+            RawVector(vel2 - vel1)
+        })
+    }
+
     /// Are the limits for this joint enabled?
+    ///
+    /// Only `PrismaticJoint` carries `limits_enabled`/`limits` in this rapier version —
+    /// `RevoluteJoint` and `BallJoint` don't expose an equivalent angular limit, so there's no
+    /// limit surface to wire up for them here.
     pub fn jointLimitsEnabled(&self, handle: u32) -> bool {
         self.map(handle, |j| match &j.params {
             JointParams::PrismaticJoint(p) => p.limits_enabled,
@@ -216,6 +402,11 @@ impl RawJointSet {
         })
     }
 
+    // The motor configuration below (`configure_motor_velocity`/`_position`/`_motor`) is already
+    // the full per-joint motor surface this rapier version exposes, combining target and velocity
+    // in one call. There's no JointMotionMode/MotorDriveMode type, or a way to lock/limit/free an
+    // individual axis independently of the others, to build a per-axis drive mode on top of.
+
     pub fn jointConfigureMotorModel(&mut self, handle: u32, model: RawSpringModel) {
         let model = match model {
             RawSpringModel::Disabled => SpringModel::Disabled,
@@ -322,13 +513,9 @@ impl RawJointSet {
         damping: f32,
     ) {
         self.map_mut(handle, |j| match &mut j.params {
-            JointParams::PrismaticJoint(j) => {
-                j.configure_motor_position(targetPos, stiffness, damping)
-            }
+            JointParams::PrismaticJoint(j) => j.configure_motor_position(targetPos, stiffness, damping),
             #[cfg(feature = "dim3")]
-            JointParams::RevoluteJoint(j) => {
-                j.configure_motor_position(targetPos, stiffness, damping)
-            }
+            JointParams::RevoluteJoint(j) => j.configure_motor_position(targetPos, stiffness, damping),
             JointParams::BallJoint(_j) =>
             {
                 #[cfg(feature = "dim2")]
@@ -369,11 +556,16 @@ pub struct RawJointParams(pub(crate) JointParams);
 
 #[wasm_bindgen]
 impl RawJointParams {
+    // Cylindrical, planar, and rectangular joints would each be a `PrismaticJoint`/`RevoluteJoint`
+    // combination with specific axes locked or freed per-axis. With no generic per-axis joint
+    // model to compose that from (see the module note at the top of this file), there's no way to
+    // add them here as thin builders over the existing four variants.
+
     /// Create a new joint descriptor that builds Ball joints.
     ///
     /// A ball joints allows three relative rotational degrees of freedom
-    /// by preventing any relative translation between the anchors of the
-    /// two attached rigid-bodies.
+    /// (one, in 2D) by preventing any relative translation between the
+    /// anchors of the two attached rigid-bodies.
     pub fn ball(anchor1: &RawVector, anchor2: &RawVector) -> Self {
         Self(BallJoint::new(anchor1.0.into(), anchor2.0.into()).into())
     }